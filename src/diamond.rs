@@ -214,11 +214,11 @@ impl CellData {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tests::*;
+    use crate::GridMap;
 
     #[test]
     fn test_empty() {
-        let mut map = ArrayMapProvider::new((5, 5));
+        let mut map = GridMap::new((5, 5));
         let mut alg = DiamondLos::new(5);
         alg.compute_los(Coord(2, 2), 10, &mut map);
 
@@ -235,7 +235,7 @@ mod tests {
 
     #[test]
     fn test_vision_field() {
-        let mut map = ArrayMapProvider::new((5, 5));
+        let mut map = GridMap::new((5, 5));
         let mut alg = DiamondLos::new(4);
         alg.compute_los(Coord(1, 0), 4, &mut map);
 
@@ -252,7 +252,7 @@ mod tests {
 
     #[test]
     fn test_vision_walls_aligned() {
-        let mut map = ArrayMapProvider::new((5, 5));
+        let mut map = GridMap::new((5, 5));
         map.set_wall(Coord(2, 0), true);
         map.set_wall(Coord(3, 0), true);
         map.set_wall(Coord(0, 2), true);
@@ -272,7 +272,7 @@ mod tests {
 
     #[test]
     fn test_vision_walls() {
-        let mut map = ArrayMapProvider::new((5, 5));
+        let mut map = GridMap::new((5, 5));
         map.set_wall(Coord(3, 1), true);
         map.set_wall(Coord(2, 2), true);
         let mut alg = DiamondLos::new(5);
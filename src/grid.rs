@@ -0,0 +1,156 @@
+use std::fmt;
+use std::fmt::Write;
+
+use super::{Coord, Map, MapProvider};
+
+/// A batteries-included dense grid map.
+///
+/// `GridMap` stores, for every cell, whether it blocks the view and whether it
+/// is currently visible. It also keeps a persistent *explored* bitmap, separate
+/// from the per-frame *visible* one, so callers can implement fog-of-war: a cell
+/// stays explored once seen, even after [`reset`](GridMap::reset) clears the
+/// visible state between frames.
+///
+/// It implements [`MapProvider`], so it can be passed directly to any
+/// [`LosAlgorithm`](crate::LosAlgorithm).
+#[derive(Clone)]
+pub struct GridMap {
+    size: (usize, usize),
+    walls: Map<bool>,
+    visible: Map<bool>,
+    explored: Map<bool>,
+}
+
+impl GridMap {
+    /// Create an empty map of the given `(width, height)`.
+    pub fn new(size: (usize, usize)) -> Self {
+        Self {
+            size,
+            walls: Map::new(size, false),
+            visible: Map::new(size, false),
+            explored: Map::new(size, false),
+        }
+    }
+
+    /// Create a map of the given `(width, height)`, marking every coordinate
+    /// yielded by `blocking` as a wall.
+    pub fn from_blocking<I>(size: (usize, usize), blocking: I) -> Self
+    where
+        I: IntoIterator<Item = Coord>,
+    {
+        let mut map = Self::new(size);
+        map.set_blocking(blocking);
+        map
+    }
+
+    /// Set whether the cell at `coord` blocks the view.
+    pub fn set_wall(&mut self, coord: Coord, value: bool) {
+        self.walls[coord] = value;
+    }
+
+    /// Mark every coordinate yielded by `blocking` as a wall.
+    pub fn set_blocking<I>(&mut self, blocking: I)
+    where
+        I: IntoIterator<Item = Coord>,
+    {
+        for coord in blocking {
+            self.walls[coord] = true;
+        }
+    }
+
+    /// Return true if the cell at `coord` blocks the view.
+    pub fn is_wall(&self, coord: Coord) -> bool {
+        self.walls[coord]
+    }
+
+    /// Return true if the cell at `coord` is visible in the current frame.
+    pub fn is_visible(&self, coord: Coord) -> bool {
+        self.visible[coord]
+    }
+
+    /// Return true if the cell at `coord` has ever been visible.
+    pub fn is_explored(&self, coord: Coord) -> bool {
+        self.explored[coord]
+    }
+
+    /// Clear the per-frame visible state. Explored cells are left untouched.
+    pub fn reset(&mut self) {
+        self.visible.inner.iter_mut().for_each(|c| *c = false);
+    }
+
+    /// Iterate over the visible result map.
+    pub fn iter_result<'a>(&'a self) -> impl Iterator<Item = (Coord, bool)> + 'a {
+        (0..self.size.1).flat_map(move |y| {
+            (0..self.size.0).map(move |x| {
+                let coord = Coord(x as i32, y as i32);
+                (coord, self.visible[coord])
+            })
+        })
+    }
+}
+
+impl fmt::Debug for GridMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.size.1 {
+            f.write_char('[')?;
+            for x in 0..self.size.0 {
+                let coord = Coord(x as i32, y as i32);
+                match (self.walls[coord], self.visible[coord]) {
+                    (false, false) => f.write_char(' '),
+                    (false, true) => f.write_char('.'),
+                    (true, false) => f.write_char('x'),
+                    (true, true) => f.write_char('X'),
+                }?
+            }
+            f.write_str("]\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl MapProvider for GridMap {
+    fn is_blocking(&self, coord: Coord) -> bool {
+        self.walls[coord]
+    }
+
+    fn bounds(&self) -> (Coord, Coord) {
+        (
+            Coord(0, 0),
+            Coord(self.size.0 as i32 - 1, self.size.1 as i32 - 1),
+        )
+    }
+
+    fn mark_as_visible(&mut self, coord: Coord) {
+        self.visible[coord] = true;
+        self.explored[coord] = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explored_persists_across_reset() {
+        let mut map = GridMap::new((3, 3));
+        map.mark_as_visible(Coord(1, 1));
+
+        assert!(map.is_visible(Coord(1, 1)));
+        assert!(map.is_explored(Coord(1, 1)));
+
+        map.reset();
+
+        assert!(!map.is_visible(Coord(1, 1)));
+        assert!(map.is_explored(Coord(1, 1)));
+    }
+
+    #[test]
+    fn test_from_blocking() {
+        let map = GridMap::from_blocking((3, 3), [Coord(0, 0), Coord(2, 1)]);
+
+        assert!(map.is_wall(Coord(0, 0)));
+        assert!(map.is_wall(Coord(2, 1)));
+        assert!(!map.is_wall(Coord(1, 1)));
+    }
+}
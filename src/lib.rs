@@ -1,8 +1,12 @@
 use std::ops::{Add, Index, IndexMut};
 
 mod diamond;
+mod grid;
+mod shadowcast;
 
 pub use diamond::*;
+pub use grid::*;
+pub use shadowcast::*;
 
 /// Trait for Line-of-sight calculation algorithm.
 pub trait LosAlgorithm {
@@ -25,11 +29,37 @@ pub trait MapProvider {
 }
 
 /// 2D Coordinates
-///
-/// TOOD: use u32?
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub struct Coord(pub i32, pub i32);
 
+impl From<(i32, i32)> for Coord {
+    fn from((x, y): (i32, i32)) -> Self {
+        Coord(x, y)
+    }
+}
+
+impl From<Coord> for (i32, i32) {
+    fn from(coord: Coord) -> Self {
+        (coord.0, coord.1)
+    }
+}
+
+/// Conversions to and from [`euclid::Point2D`], so callers can cast in and out
+/// of their own point type. The unit parameter `U` is discarded.
+#[cfg(feature = "euclid")]
+impl<U> From<euclid::Point2D<i32, U>> for Coord {
+    fn from(point: euclid::Point2D<i32, U>) -> Self {
+        Coord(point.x, point.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<U> From<Coord> for euclid::Point2D<i32, U> {
+    fn from(coord: Coord) -> Self {
+        euclid::Point2D::new(coord.0, coord.1)
+    }
+}
+
 impl Add<Coord> for Coord {
     type Output = Coord;
 
@@ -49,6 +79,7 @@ impl Add<(i32, i32)> for Coord {
 /// 2D map implemented as a vector
 #[derive(Clone, Debug, Default)]
 struct Map<T> {
+    width: usize,
     height: usize,
     pub inner: Vec<T>,
 }
@@ -59,6 +90,7 @@ impl<T> Map<T> {
         T: Clone,
     {
         Self {
+            width,
             height,
             inner: vec![initial_value; width * height],
         }
@@ -70,7 +102,7 @@ impl<T> Map<T> {
             index.1 >= 0
                 && (index.1 as usize) < self.height
                 && index.0 >= 0
-                && (index.0 as usize) < self.inner.len() / self.height,
+                && (index.0 as usize) < self.width,
             "coord is out of bounds: {:?}",
             index
         );
@@ -84,7 +116,7 @@ impl<T> Index<Coord> for Map<T> {
     fn index(&self, index: Coord) -> &Self::Output {
         self.assert_in_bounds(index);
 
-        &self.inner[index.1 as usize * self.height + index.0 as usize]
+        &self.inner[index.1 as usize * self.width + index.0 as usize]
     }
 }
 
@@ -93,98 +125,31 @@ impl<T> IndexMut<Coord> for Map<T> {
     fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
         self.assert_in_bounds(index);
 
-        &mut self.inner[index.1 as usize * self.height + index.0 as usize]
+        &mut self.inner[index.1 as usize * self.width + index.0 as usize]
     }
 }
 
 #[cfg(test)]
-pub mod tests {
-    use std::fmt;
-
-    use crate::{Coord, Map, MapProvider};
-    use std::fmt::Write;
+mod tests {
+    use super::{Coord, Map};
 
-    #[derive(Clone)]
-    pub struct ArrayMapProvider {
-        size: (usize, usize),
-        visible: Map<bool>,
-        map: Map<bool>,
+    #[test]
+    fn coord_tuple_conversions() {
+        assert_eq!(Coord::from((2, 3)), Coord(2, 3));
+        assert_eq!(<(i32, i32)>::from(Coord(2, 3)), (2, 3));
     }
 
-    impl ArrayMapProvider {
-        pub fn new(size: (usize, usize)) -> Self {
-            Self {
-                size,
-                visible: Map::new(size, false),
-                map: Map::new(size, false),
-            }
-        }
-
-        /// Remove marked visible cells
-        pub fn reset(&mut self) {
-            self.visible.inner.iter_mut().for_each(|c| *c = false);
-        }
-
-        pub fn set_wall(&mut self, coord: Coord, value: bool) {
-            self.map[coord] = value;
-        }
-
-        /// Iterate over visible result map
-        pub fn iter_result<'a>(&'a self) -> impl Iterator<Item = (Coord, bool)> + 'a {
-            (0..self.size.1).flat_map(move |y| {
-                (0..self.size.0).map(move |x| {
-                    (
-                        Coord(x as i32, y as i32),
-                        self.visible[Coord(x as i32, y as i32)],
-                    )
-                })
-            })
-        }
-
-        fn in_bounds(&self, coord: Coord) -> bool {
-            coord.0 >= 0
-                && (coord.0 as usize) < self.size.0
-                && coord.1 >= 0
-                && (coord.1 as usize) < self.size.1
-        }
-    }
-
-    impl fmt::Debug for ArrayMapProvider {
-        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            for y in 0..self.size.1 {
-                f.write_char('[')?;
-                for x in 0..self.size.0 {
-                    match (
-                        self.map.inner[y * self.size.1 + x],
-                        self.visible.inner[y * self.size.1 + x],
-                    ) {
-                        (false, false) => f.write_char(' '),
-                        (false, true) => f.write_char('.'),
-                        (true, false) => f.write_char('x'),
-                        (true, true) => f.write_char('X'),
-                    }?
-                }
-                f.write_str("]\n")?;
-            }
-
-            Ok(())
-        }
-    }
-
-    impl MapProvider for ArrayMapProvider {
-        fn is_blocking(&self, cell: Coord) -> bool {
-            self.map[cell]
-        }
-
-        fn bounds(&self) -> (Coord, Coord) {
-            (
-                Coord(0, 0),
-                Coord(self.size.0 as i32 - 1, self.size.1 as i32 - 1),
-            )
-        }
-
-        fn mark_as_visible(&mut self, cell: Coord) {
-            self.visible[cell] = true;
-        }
+    /// A rectangular map must not alias cells: with the old `y * height + x`
+    /// stride, `(2, 0)` and `(0, 1)` both landed on index 2.
+    #[test]
+    fn rectangular_map_does_not_alias() {
+        let mut map = Map::new((3, 2), 0);
+        map[Coord(2, 0)] = 1;
+        map[Coord(0, 1)] = 2;
+
+        assert_eq!(map[Coord(2, 0)], 1);
+        assert_eq!(map[Coord(0, 1)], 2);
+        assert_eq!(map.inner, vec![0, 0, 1, 2, 0, 0]);
     }
 }
+
@@ -0,0 +1,239 @@
+use super::{Coord, LosAlgorithm, MapProvider};
+
+/// Implementation of symmetric recursive shadowcasting.
+///
+/// This follows Albert Ford's description of the algorithm, see
+/// <https://www.albertford.com/shadowcasting/>.
+///
+/// Unlike [`DiamondLos`](crate::DiamondLos), shadowcasting is symmetric: if the
+/// origin can see a tile, then that tile can see the origin. It also produces
+/// cleaner wall handling. It keeps no per-call cache, so the same instance can
+/// be reused freely.
+#[derive(Clone, Debug, Default)]
+pub struct ShadowcastLos;
+
+impl ShadowcastLos {
+    pub fn new() -> Self {
+        ShadowcastLos
+    }
+}
+
+/// A signed rational number, used to avoid floating-point rounding errors when
+/// comparing slopes. The denominator is always kept strictly positive.
+#[derive(Copy, Clone, Debug)]
+struct Fraction {
+    num: i32,
+    den: i32,
+}
+
+impl Fraction {
+    fn new(num: i32, den: i32) -> Self {
+        if den < 0 {
+            Fraction {
+                num: -num,
+                den: -den,
+            }
+        } else {
+            Fraction { num, den }
+        }
+    }
+
+    /// Multiply by an integer, returning the resulting fraction.
+    fn mul(self, rhs: i32) -> Fraction {
+        Fraction {
+            num: self.num * rhs,
+            den: self.den,
+        }
+    }
+
+    /// `floor(self + 1/2)`, rounding halves up.
+    fn round_ties_up(self) -> i32 {
+        (2 * self.num + self.den).div_euclid(2 * self.den)
+    }
+
+    /// `ceil(self - 1/2)`, rounding halves down.
+    fn round_ties_down(self) -> i32 {
+        -((self.den - 2 * self.num).div_euclid(2 * self.den))
+    }
+}
+
+/// The slope of the line separating tile `col` from the previous one at `depth`.
+fn slope(depth: i32, col: i32) -> Fraction {
+    Fraction::new(2 * col - 1, 2 * depth)
+}
+
+/// One of the four cardinal quadrants scanned around the origin.
+#[derive(Copy, Clone)]
+enum Quadrant {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Quadrant {
+    const ALL: [Quadrant; 4] = [
+        Quadrant::North,
+        Quadrant::East,
+        Quadrant::South,
+        Quadrant::West,
+    ];
+
+    /// Map a `(depth, col)` pair to a real map coordinate around `origin`.
+    fn transform(self, origin: Coord, depth: i32, col: i32) -> Coord {
+        match self {
+            Quadrant::North => origin + Coord(col, -depth),
+            Quadrant::South => origin + Coord(col, depth),
+            Quadrant::East => origin + Coord(depth, col),
+            Quadrant::West => origin + Coord(-depth, col),
+        }
+    }
+}
+
+/// A row to scan, parametrized by depth and the slopes of its angular bounds.
+#[derive(Copy, Clone)]
+struct Row {
+    depth: i32,
+    start_slope: Fraction,
+    end_slope: Fraction,
+}
+
+impl Row {
+    /// Whether tile `col` is "symmetric", i.e. its center falls within the row's
+    /// angular bounds.
+    fn is_symmetric(&self, col: i32) -> bool {
+        // col >= depth * start_slope && col <= depth * end_slope
+        col * self.start_slope.den >= self.depth * self.start_slope.num
+            && col * self.end_slope.den <= self.depth * self.end_slope.num
+    }
+}
+
+impl ShadowcastLos {
+    fn scan_quadrant<M: MapProvider>(
+        quadrant: Quadrant,
+        origin: Coord,
+        vision_range: u32,
+        map: &mut M,
+    ) {
+        let mut rows = vec![Row {
+            depth: 1,
+            start_slope: Fraction::new(-1, 1),
+            end_slope: Fraction::new(1, 1),
+        }];
+
+        while let Some(mut row) = rows.pop() {
+            if row.depth as u32 > vision_range {
+                continue;
+            }
+
+            let depth = row.depth;
+            let min_col = row.start_slope.mul(depth).round_ties_up();
+            let max_col = row.end_slope.mul(depth).round_ties_down();
+
+            let mut prev_wall: Option<bool> = None;
+            for col in min_col..=max_col {
+                let coord = quadrant.transform(origin, depth, col);
+                let in_bounds = is_in_bounds(coord, map);
+                // Out-of-bounds tiles block the view like walls.
+                let is_wall = !in_bounds || map.is_blocking(coord);
+
+                if in_bounds && (is_wall || row.is_symmetric(col)) {
+                    map.mark_as_visible(coord);
+                }
+
+                if let Some(was_wall) = prev_wall {
+                    if was_wall && !is_wall {
+                        row.start_slope = slope(depth, col);
+                    }
+                    if !was_wall && is_wall {
+                        rows.push(Row {
+                            depth: depth + 1,
+                            start_slope: row.start_slope,
+                            end_slope: slope(depth, col),
+                        });
+                    }
+                }
+
+                prev_wall = Some(is_wall);
+            }
+
+            if prev_wall == Some(false) {
+                rows.push(Row {
+                    depth: depth + 1,
+                    start_slope: row.start_slope,
+                    end_slope: row.end_slope,
+                });
+            }
+        }
+    }
+}
+
+fn is_in_bounds<M: MapProvider>(cell: Coord, map: &M) -> bool {
+    let bounds = map.bounds();
+
+    cell.0 >= (bounds.0).0
+        && cell.0 <= (bounds.1).0
+        && cell.1 >= (bounds.0).1
+        && cell.1 <= (bounds.1).1
+}
+
+impl LosAlgorithm for ShadowcastLos {
+    fn compute_los<M: MapProvider>(&mut self, origin: Coord, vision_range: u32, map: &mut M) {
+        map.mark_as_visible(origin);
+
+        for quadrant in Quadrant::ALL {
+            Self::scan_quadrant(quadrant, origin, vision_range, map);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridMap;
+
+    #[test]
+    fn test_empty() {
+        let mut map = GridMap::new((5, 5));
+        let mut alg = ShadowcastLos::new();
+        alg.compute_los(Coord(2, 2), 10, &mut map);
+
+        let map_str = format!("{:?}", map);
+        let expected_str = "\
+[.....]
+[.....]
+[.....]
+[.....]
+[.....]
+";
+        assert_eq!(map_str, expected_str);
+    }
+
+    /// The defining property of shadowcasting: visibility is symmetric.
+    #[test]
+    fn test_symmetry() {
+        let mut map = GridMap::new((7, 7));
+        map.set_wall(Coord(3, 2), true);
+        map.set_wall(Coord(4, 4), true);
+        map.set_wall(Coord(2, 5), true);
+
+        let mut alg = ShadowcastLos::new();
+        alg.compute_los(Coord(3, 3), 10, &mut map);
+
+        let visible: Vec<Coord> = map
+            .iter_result()
+            .filter_map(|(coord, visible)| if visible { Some(coord) } else { None })
+            .collect();
+
+        for &coord in &visible {
+            let mut other = map.clone();
+            other.reset();
+            alg.compute_los(coord, 10, &mut other);
+            assert!(
+                other.iter_result().any(|(c, v)| v && c == Coord(3, 3)),
+                "origin should be visible from {:?}",
+                coord
+            );
+        }
+    }
+}